@@ -0,0 +1,313 @@
+//! Declarative cleanup manifests.
+//!
+//! Inspired by helmfile's `ReleaseSetSpec`, a cleanup manifest describes a
+//! batch of releases (and optionally whole namespaces) to tear down, so that
+//! a single YAML file can be replayed reproducibly from CI instead of
+//! driving the interactive `uninstall` flow by hand.
+
+use crate::release::list_release_details;
+use anyhow::{Context, Result};
+use kube::{api::ListParams, Api, Client, ResourceExt};
+use k8s_openapi::api::core::v1::Secret;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Top-level declarative cleanup manifest (`--file cleanup.yaml`).
+#[derive(Debug, Deserialize)]
+pub struct CleanupSpec {
+    #[serde(default)]
+    pub releases: Vec<ReleaseSpec>,
+    #[serde(default)]
+    pub environments: HashMap<String, EnvironmentOverride>,
+}
+
+/// One entry in the manifest: either a named release, or a selector that
+/// resolves to zero or more releases in a namespace at apply time.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseSpec {
+    pub name: Option<String>,
+    pub namespace: String,
+    #[serde(default)]
+    pub delete_namespace: bool,
+    #[serde(default)]
+    pub selector: Option<Selector>,
+    #[serde(default)]
+    pub pre_uninstall: Option<String>,
+    #[serde(default)]
+    pub post_uninstall: Option<String>,
+}
+
+/// Selects releases by secret label or by Helm release status.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Selector {
+    Label { key: String, value: String },
+    Status(String),
+}
+
+/// Per-environment overrides, applied on top of a `ReleaseSpec` when the
+/// manifest is applied with `--environment <name>`.
+///
+/// `namespace`/`delete_namespace` are a fallback applied to every release in
+/// the environment; `releases` keys in a per-release override by release
+/// name, for manifests where different releases land in different
+/// namespaces per environment (e.g. `app1` in `app1-staging`, `app2` in
+/// `app2-staging`).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EnvironmentOverride {
+    #[serde(default)]
+    pub namespace: Option<String>,
+    #[serde(default)]
+    pub delete_namespace: Option<bool>,
+    #[serde(default)]
+    pub releases: HashMap<String, ReleaseOverride>,
+}
+
+/// A single release's override within an [`EnvironmentOverride`].
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReleaseOverride {
+    #[serde(default)]
+    pub namespace: Option<String>,
+    #[serde(default)]
+    pub delete_namespace: Option<bool>,
+}
+
+/// A fully resolved release, ready to be passed to `helm_uninstall`.
+#[derive(Debug, Clone)]
+pub struct ResolvedRelease {
+    pub name: String,
+    pub namespace: String,
+    pub delete_namespace: bool,
+    pub pre_uninstall: Option<String>,
+    pub post_uninstall: Option<String>,
+}
+
+/// Parse a cleanup manifest from disk.
+pub fn load_spec(path: &Path) -> Result<CleanupSpec> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read cleanup manifest '{}'", path.display()))?;
+    serde_yaml::from_str(&raw)
+        .with_context(|| format!("failed to parse cleanup manifest '{}'", path.display()))
+}
+
+/// Resolve every `ReleaseSpec` in the manifest into concrete releases,
+/// applying the named environment's overrides (if any) and expanding
+/// selectors against the live cluster.
+pub async fn resolve_releases(
+    client: &Client,
+    spec: &CleanupSpec,
+    environment: Option<&str>,
+) -> Result<Vec<ResolvedRelease>> {
+    let env_override = match environment {
+        Some(name) => Some(
+            spec.environments
+                .get(name)
+                .with_context(|| format!("environment '{}' is not defined in the manifest", name))?,
+        ),
+        None => None,
+    };
+
+    let mut resolved = Vec::new();
+    for release_spec in &spec.releases {
+        match (&release_spec.name, &release_spec.selector) {
+            (Some(name), _) => {
+                let (namespace, delete_namespace) = resolve_namespace_and_delete(
+                    env_override,
+                    name,
+                    &release_spec.namespace,
+                    release_spec.delete_namespace,
+                );
+                resolved.push(ResolvedRelease {
+                    name: name.clone(),
+                    namespace,
+                    delete_namespace,
+                    pre_uninstall: release_spec.pre_uninstall.clone(),
+                    post_uninstall: release_spec.post_uninstall.clone(),
+                })
+            }
+            (None, Some(selector)) => {
+                // Selector matches are searched for in the environment's
+                // blanket namespace (or the manifest default); a per-release
+                // override can still redirect an individual match once its
+                // name is known.
+                let search_namespace = env_override
+                    .and_then(|o| o.namespace.clone())
+                    .unwrap_or_else(|| release_spec.namespace.clone());
+                for name in resolve_selector(client, &search_namespace, selector).await? {
+                    let (namespace, delete_namespace) = resolve_namespace_and_delete(
+                        env_override,
+                        &name,
+                        &release_spec.namespace,
+                        release_spec.delete_namespace,
+                    );
+                    resolved.push(ResolvedRelease {
+                        name,
+                        namespace,
+                        delete_namespace,
+                        pre_uninstall: release_spec.pre_uninstall.clone(),
+                        post_uninstall: release_spec.post_uninstall.clone(),
+                    });
+                }
+            }
+            (None, None) => {
+                anyhow::bail!(
+                    "release entry for namespace '{}' has neither a name nor a selector",
+                    release_spec.namespace
+                );
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Resolve a single release's namespace/delete_namespace across all three
+/// precedence levels: a per-release override
+/// (`environments.<name>.releases.<release>`) wins over the environment's
+/// blanket override, which wins over the manifest's own default for that
+/// `ReleaseSpec`.
+fn resolve_namespace_and_delete(
+    env_override: Option<&EnvironmentOverride>,
+    release_name: &str,
+    spec_namespace: &str,
+    spec_delete_namespace: bool,
+) -> (String, bool) {
+    let release_override = env_override.and_then(|o| o.releases.get(release_name));
+
+    let namespace = release_override
+        .and_then(|o| o.namespace.clone())
+        .or_else(|| env_override.and_then(|o| o.namespace.clone()))
+        .unwrap_or_else(|| spec_namespace.to_string());
+    let delete_namespace = release_override
+        .and_then(|o| o.delete_namespace)
+        .or_else(|| env_override.and_then(|o| o.delete_namespace))
+        .unwrap_or(spec_delete_namespace);
+
+    (namespace, delete_namespace)
+}
+
+/// Expand a selector into the set of release names it currently matches.
+async fn resolve_selector(client: &Client, ns: &str, selector: &Selector) -> Result<Vec<String>> {
+    match selector {
+        Selector::Label { key, value } => {
+            let api: Api<Secret> = Api::namespaced(client.clone(), ns);
+            let lp = ListParams::default().labels(&format!("{}={}", key, value));
+            let secrets = api.list(&lp).await?;
+
+            let mut names = BTreeMap::new();
+            for s in secrets {
+                if let Some(name) = s.labels().get("name") {
+                    names.insert(name.clone(), ());
+                }
+            }
+            Ok(names.into_keys().collect())
+        }
+        Selector::Status(status) => {
+            let releases = list_release_details(client, ns).await?;
+            Ok(releases
+                .into_iter()
+                .filter(|r| &r.status == status)
+                .map(|r| r.name)
+                .collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_with(namespace: Option<&str>, delete_namespace: Option<bool>) -> EnvironmentOverride {
+        EnvironmentOverride {
+            namespace: namespace.map(str::to_string),
+            delete_namespace,
+            releases: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn falls_back_to_manifest_default_with_no_environment() {
+        let (namespace, delete_namespace) =
+            resolve_namespace_and_delete(None, "app1", "app1-dev", false);
+        assert_eq!(namespace, "app1-dev");
+        assert!(!delete_namespace);
+    }
+
+    #[test]
+    fn environment_blanket_override_wins_over_manifest_default() {
+        let env = env_with(Some("app1-staging"), Some(true));
+        let (namespace, delete_namespace) =
+            resolve_namespace_and_delete(Some(&env), "app1", "app1-dev", false);
+        assert_eq!(namespace, "app1-staging");
+        assert!(delete_namespace);
+    }
+
+    #[test]
+    fn per_release_override_wins_over_environment_blanket_override() {
+        let mut env = env_with(Some("shared-staging"), Some(true));
+        env.releases.insert(
+            "app2".to_string(),
+            ReleaseOverride {
+                namespace: Some("app2-staging".to_string()),
+                delete_namespace: Some(false),
+            },
+        );
+
+        // A different release in the same environment still gets the
+        // blanket override...
+        let (app1_ns, app1_del) = resolve_namespace_and_delete(Some(&env), "app1", "app1-dev", false);
+        assert_eq!(app1_ns, "shared-staging");
+        assert!(app1_del);
+
+        // ...but "app2" is redirected to its own namespace by its
+        // per-release override, which is the whole point of the feature:
+        // one manifest, multiple releases, each landing in a distinct
+        // namespace per environment.
+        let (app2_ns, app2_del) = resolve_namespace_and_delete(Some(&env), "app2", "app2-dev", false);
+        assert_eq!(app2_ns, "app2-staging");
+        assert!(!app2_del);
+    }
+
+    #[test]
+    fn per_release_override_can_set_only_one_field() {
+        let mut env = env_with(Some("shared-staging"), Some(true));
+        env.releases.insert(
+            "app3".to_string(),
+            ReleaseOverride {
+                namespace: Some("app3-staging".to_string()),
+                delete_namespace: None,
+            },
+        );
+
+        // Namespace comes from the per-release override, but
+        // delete_namespace isn't set there, so it falls through to the
+        // environment's blanket override rather than the manifest default.
+        let (namespace, delete_namespace) =
+            resolve_namespace_and_delete(Some(&env), "app3", "app3-dev", false);
+        assert_eq!(namespace, "app3-staging");
+        assert!(delete_namespace);
+    }
+
+    #[test]
+    fn selector_match_can_be_redirected_to_a_different_namespace_than_it_was_found_in() {
+        // Selectors are searched for in the environment's blanket namespace
+        // (the manifest default if no environment), but once a match's name
+        // is known, a per-release override can still send that specific
+        // release somewhere else entirely.
+        let search_namespace = "apps-staging";
+        let mut env = env_with(Some(search_namespace), None);
+        env.releases.insert(
+            "quarantined-app".to_string(),
+            ReleaseOverride {
+                namespace: Some("quarantine".to_string()),
+                delete_namespace: None,
+            },
+        );
+
+        let (namespace, _) =
+            resolve_namespace_and_delete(Some(&env), "quarantined-app", "apps-dev", false);
+        assert_ne!(namespace, search_namespace);
+        assert_eq!(namespace, "quarantine");
+    }
+}
@@ -0,0 +1,60 @@
+//! Pre/post-uninstall lifecycle hooks.
+//!
+//! A hook is an arbitrary shell command the user supplies to drain traffic,
+//! snapshot PVCs, send notifications, etc. around teardown. It receives
+//! context via environment variables rather than arguments, and its stderr
+//! is forwarded straight through for real diagnostics while stdout is
+//! treated as informational only.
+
+use anyhow::{bail, Context, Result};
+use std::io::{BufRead, BufReader};
+use std::process::{Command as StdCommand, Stdio};
+
+/// Which lifecycle phase a hook is running in.
+#[derive(Debug, Clone, Copy)]
+pub enum HookPhase {
+    Pre,
+    Post,
+}
+
+impl HookPhase {
+    fn as_str(&self) -> &'static str {
+        match self {
+            HookPhase::Pre => "pre-uninstall",
+            HookPhase::Post => "post-uninstall",
+        }
+    }
+}
+
+/// Run a hook script for `release` in `namespace`. The pre-uninstall phase
+/// should abort the caller's uninstall of this release if it returns `Err`.
+pub fn run_hook(cmd: &str, release: &str, namespace: &str, phase: HookPhase) -> Result<()> {
+    println!("Running {} hook: {}", phase.as_str(), cmd);
+
+    let mut child = StdCommand::new("sh")
+        .arg("-c")
+        .arg(cmd)
+        .env("HELM_CLEANER_RELEASE", release)
+        .env("HELM_CLEANER_NAMESPACE", namespace)
+        .env("HELM_CLEANER_PHASE", phase.as_str())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .with_context(|| format!("failed to run {} hook '{}'", phase.as_str(), cmd))?;
+
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines() {
+            println!("[hook] {}", line?);
+        }
+    }
+
+    let status = child
+        .wait()
+        .with_context(|| format!("failed to wait on {} hook '{}'", phase.as_str(), cmd))?;
+
+    if !status.success() {
+        bail!("{} hook '{}' exited with {}", phase.as_str(), cmd, status);
+    }
+
+    Ok(())
+}
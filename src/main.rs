@@ -1,12 +1,25 @@
+mod cleanup;
+mod hooks;
+mod release;
+mod retry;
+mod wait;
+
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand, CommandFactory};
 use clap_complete::{generate, shells::Bash};
+use cleanup::{load_spec, resolve_releases};
 use dialoguer::{theme::ColorfulTheme, Select};
+use hooks::{run_hook, HookPhase};
 use kube::{api::ListParams, Api, Client, ResourceExt};
 use k8s_openapi::api::core::v1::{Namespace, Secret};
-use std::collections::BTreeSet;
+use release::{list_release_details, print_release_table};
+use retry::retry_with_backoff;
+use std::collections::{BTreeSet, HashMap};
 use std::io;
+use std::path::PathBuf;
 use std::process::Command as StdCommand;
+use std::time::Duration;
+use wait::{wait_for_namespace_gone, wait_for_release_gone};
 
 /// Helm Cleaner CLI
 #[derive(Parser, Debug)]
@@ -35,6 +48,60 @@ enum Commands {
         /// Skip confirmation prompts
         #[arg(long)]
         force: bool,
+
+        /// Block until all release-owned resources are actually gone
+        #[arg(long)]
+        wait: bool,
+
+        /// Timeout in seconds for `--wait`
+        #[arg(long, default_value_t = 300)]
+        timeout: u64,
+
+        /// Retry failed uninstall/delete operations with exponential backoff
+        #[arg(long, default_value_t = 0)]
+        retries: u32,
+
+        /// Shell command to run before uninstalling each release
+        #[arg(long)]
+        pre_uninstall: Option<String>,
+
+        /// Shell command to run after uninstalling each release
+        #[arg(long)]
+        post_uninstall: Option<String>,
+
+        /// Show what would be done without uninstalling or deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// List Helm releases with revision, status, chart and update time
+    List {
+        /// Kubernetes namespace
+        #[arg(short, long)]
+        namespace: String,
+    },
+
+    /// Apply a declarative cleanup manifest (batch uninstall)
+    Apply {
+        /// Path to the cleanup manifest YAML file
+        #[arg(short, long)]
+        file: PathBuf,
+
+        /// Environment overrides to apply (must exist in the manifest)
+        #[arg(short, long)]
+        environment: Option<String>,
+
+        /// Skip confirmation prompts
+        #[arg(long)]
+        force: bool,
+
+        /// Retry failed uninstall/delete operations with exponential backoff
+        #[arg(long, default_value_t = 0)]
+        retries: u32,
+
+        /// Show what would be done without uninstalling or deleting anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Generate bash completions
@@ -51,11 +118,124 @@ async fn main() -> Result<()> {
             generate(Bash, &mut Args::command(), "helm-cleaner", &mut io::stdout());
             return Ok(());
         }
+        Commands::List { namespace } => {
+            let client = Client::try_default().await?;
+            let releases = list_release_details(&client, &namespace).await?;
+            if releases.is_empty() {
+                println!("No Helm releases found in namespace '{}'", namespace);
+                return Ok(());
+            }
+            print_release_table(&releases);
+        }
+        Commands::Apply {
+            file,
+            environment,
+            force,
+            retries,
+            dry_run,
+        } => {
+            let client = Client::try_default().await?;
+
+            let spec = load_spec(&file)?;
+            let resolved = resolve_releases(&client, &spec, environment.as_deref()).await?;
+
+            if resolved.is_empty() {
+                println!("No releases matched the cleanup manifest '{}'", file.display());
+                return Ok(());
+            }
+
+            if dry_run {
+                println!("Dry run: the following would be applied from '{}':", file.display());
+                // Resolved releases can span several namespaces; list each
+                // namespace once up front instead of re-listing per release.
+                let mut details_by_namespace: HashMap<String, Vec<release::ReleaseInfo>> =
+                    HashMap::new();
+                for ns in resolved.iter().map(|r| &r.namespace).collect::<BTreeSet<_>>() {
+                    details_by_namespace.insert(ns.clone(), list_release_details(&client, ns).await?);
+                }
+                for r in &resolved {
+                    let detail = details_by_namespace
+                        .get(&r.namespace)
+                        .and_then(|infos| infos.iter().find(|info| info.name == r.name));
+                    print_plan_line(&r.name, &r.namespace, r.delete_namespace, detail);
+                }
+                return Ok(());
+            }
+
+            println!("The following releases will be removed:");
+            for r in &resolved {
+                println!(
+                    "  - {} (namespace: {}{})",
+                    r.name,
+                    r.namespace,
+                    if r.delete_namespace {
+                        ", namespace will be deleted"
+                    } else {
+                        ""
+                    }
+                );
+            }
+
+            if !force {
+                println!("Proceed? [y/N]");
+                let mut input = String::new();
+                io::stdin().read_line(&mut input)?;
+                if input.trim().to_lowercase() != "y" {
+                    println!("Aborted.");
+                    return Ok(());
+                }
+            }
+
+            let mut namespaces_to_delete = BTreeSet::new();
+            let mut removed = 0;
+            for r in &resolved {
+                if let Some(cmd) = &r.pre_uninstall {
+                    if let Err(e) = run_hook(cmd, &r.name, &r.namespace, HookPhase::Pre) {
+                        eprintln!(
+                            "⚠️  pre-uninstall hook failed for '{}', skipping: {}",
+                            r.name, e
+                        );
+                        continue;
+                    }
+                }
+
+                retry_with_backoff(retries, &format!("uninstall '{}'", r.name), || async {
+                    helm_uninstall(&r.name, &r.namespace)
+                })
+                .await?;
+                removed += 1;
+
+                if let Some(cmd) = &r.post_uninstall {
+                    if let Err(e) = run_hook(cmd, &r.name, &r.namespace, HookPhase::Post) {
+                        eprintln!("⚠️  post-uninstall hook failed for '{}': {}", r.name, e);
+                    }
+                }
+
+                if r.delete_namespace {
+                    namespaces_to_delete.insert(r.namespace.clone());
+                }
+            }
+
+            for ns in namespaces_to_delete {
+                retry_with_backoff(retries, &format!("delete namespace '{}'", ns), || async {
+                    delete_ns(&client, &ns).await
+                })
+                .await?;
+            }
+
+            println!("✅ Cleanup manifest applied: {} release(s) removed", removed);
+        }
         Commands::Uninstall {
             namespace,
             release,
             delete_namespace,
             force,
+            wait,
+            timeout,
+            retries,
+            pre_uninstall,
+            post_uninstall,
+            dry_run,
         } => {
             let client = Client::try_default().await?;
 
@@ -88,6 +268,18 @@ async fn main() -> Result<()> {
                 }
             };
 
+            if dry_run {
+                println!("Dry run: the following would be uninstalled:");
+                // List the namespace once rather than re-decoding every
+                // release secret for each selected release.
+                let details = list_release_details(&client, &namespace).await?;
+                for release in &selected_releases {
+                    let detail = details.iter().find(|info| &info.name == release);
+                    print_plan_line(release, &namespace, delete_namespace, detail);
+                }
+                return Ok(());
+            }
+
             // Confirmation prompt
             if !force {
                 if selected_releases.len() == 1 {
@@ -117,13 +309,50 @@ async fn main() -> Result<()> {
             }
 
             // Uninstall releases
+            let timeout = Duration::from_secs(timeout);
             for release in &selected_releases {
-                helm_uninstall(release, &namespace)?;
+                if let Some(cmd) = &pre_uninstall {
+                    if let Err(e) = run_hook(cmd, release, &namespace, HookPhase::Pre) {
+                        eprintln!(
+                            "⚠️  pre-uninstall hook failed for '{}', skipping: {}",
+                            release, e
+                        );
+                        continue;
+                    }
+                }
+
+                retry_with_backoff(retries, &format!("uninstall '{}'", release), || async {
+                    helm_uninstall(release, &namespace)
+                })
+                .await?;
+                if wait {
+                    retry_with_backoff(retries, &format!("wait for '{}'", release), || async {
+                        wait_for_release_gone(&client, release, &namespace, timeout).await
+                    })
+                    .await?;
+                    println!("✅ All resources for release '{}' are gone", release);
+                }
+
+                if let Some(cmd) = &post_uninstall {
+                    if let Err(e) = run_hook(cmd, release, &namespace, HookPhase::Post) {
+                        eprintln!("⚠️  post-uninstall hook failed for '{}': {}", release, e);
+                    }
+                }
             }
 
             // Delete namespace if requested
             if delete_namespace {
-                delete_ns(&client, &namespace).await?;
+                retry_with_backoff(retries, &format!("delete namespace '{}'", namespace), || async {
+                    delete_ns(&client, &namespace).await
+                })
+                .await?;
+                if wait {
+                    retry_with_backoff(retries, &format!("wait for namespace '{}'", namespace), || async {
+                        wait_for_namespace_gone(&client, &namespace, timeout).await
+                    })
+                    .await?;
+                    println!("✅ Namespace '{}' is fully removed", namespace);
+                }
             }
         }
     }
@@ -131,6 +360,27 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Print one release's entry in a dry-run plan: the exact `helm uninstall`
+/// invocation, plus the release's revision and chart when it could be
+/// decoded, followed by a namespace deletion line when `delete_namespace` is set.
+fn print_plan_line(
+    release: &str,
+    namespace: &str,
+    delete_namespace: bool,
+    detail: Option<&release::ReleaseInfo>,
+) {
+    match detail {
+        Some(info) => println!(
+            "  helm uninstall {} -n {}  # revision {}, chart {}-{}",
+            release, namespace, info.revision, info.chart_name, info.chart_version
+        ),
+        None => println!("  helm uninstall {} -n {}  # revision unknown", release, namespace),
+    }
+    if delete_namespace {
+        println!("  kubectl delete namespace {}", namespace);
+    }
+}
+
 /// List Helm releases in a namespace (sorted and deduplicated)
 async fn list_releases(client: &Client, ns: &str) -> Result<Vec<String>> {
     let api: Api<Secret> = Api::namespaced(client.clone(), ns);
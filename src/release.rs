@@ -0,0 +1,242 @@
+//! Native decoding of Helm v3 release secrets.
+//!
+//! Helm stores each release revision as a Kubernetes `Secret` of type
+//! `helm.sh/release.v1` named `sh.helm.release.v1.<release>.v<revision>`. The
+//! `data["release"]` field is itself base64-encoded gzip-compressed JSON, on
+//! top of whatever base64 decoding kube-rs already performs when it reads
+//! Secret data into bytes.
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use flate2::read::GzDecoder;
+use k8s_openapi::api::core::v1::Secret;
+use kube::{api::ListParams, Api, Client, ResourceExt};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io::Read;
+
+const RELEASE_SECRET_TYPE: &str = "helm.sh/release.v1";
+
+#[derive(Debug, Deserialize)]
+struct HelmReleasePayload {
+    name: String,
+    version: u32,
+    namespace: String,
+    info: HelmReleaseInfo,
+    chart: HelmChart,
+}
+
+#[derive(Debug, Deserialize)]
+struct HelmReleaseInfo {
+    status: String,
+    last_deployed: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct HelmChart {
+    metadata: HelmChartMetadata,
+}
+
+#[derive(Debug, Deserialize)]
+struct HelmChartMetadata {
+    name: String,
+    version: String,
+    #[serde(rename = "appVersion", default)]
+    app_version: String,
+}
+
+/// A decoded Helm release, as reported by a single `helm.sh/release.v1` Secret.
+#[derive(Debug, Clone)]
+pub struct ReleaseInfo {
+    pub name: String,
+    pub revision: u32,
+    pub namespace: String,
+    pub status: String,
+    pub chart_name: String,
+    pub chart_version: String,
+    pub app_version: String,
+    pub last_deployed: String,
+}
+
+/// Decode a Helm v3 release Secret into a [`ReleaseInfo`].
+pub fn decode_release_secret(secret: &Secret) -> Result<ReleaseInfo> {
+    let data = secret
+        .data
+        .as_ref()
+        .context("release secret has no data")?;
+    let release_field = data
+        .get("release")
+        .context("release secret is missing the 'release' key")?;
+
+    // kube-rs already base64-decoded the Secret's data into bytes; those
+    // bytes are themselves a base64 string that Helm encodes the gzip
+    // payload with.
+    let inner_b64 = &release_field.0;
+    let gzip_bytes = STANDARD
+        .decode(inner_b64)
+        .context("release payload is not valid base64")?;
+
+    let mut json_bytes = Vec::new();
+    GzDecoder::new(gzip_bytes.as_slice())
+        .read_to_end(&mut json_bytes)
+        .context("failed to gunzip release payload")?;
+
+    let payload: HelmReleasePayload = serde_json::from_slice(&json_bytes)
+        .context("failed to parse Helm release JSON")?;
+
+    Ok(ReleaseInfo {
+        name: payload.name,
+        revision: payload.version,
+        namespace: payload.namespace,
+        status: payload.info.status,
+        chart_name: payload.chart.metadata.name,
+        chart_version: payload.chart.metadata.version,
+        app_version: payload.chart.metadata.app_version,
+        last_deployed: payload.info.last_deployed,
+    })
+}
+
+/// List the latest revision of every Helm release in a namespace.
+pub async fn list_release_details(client: &Client, ns: &str) -> Result<Vec<ReleaseInfo>> {
+    let api: Api<Secret> = Api::namespaced(client.clone(), ns);
+    let lp = ListParams::default().fields(&format!("type={}", RELEASE_SECRET_TYPE));
+    let secrets = api.list(&lp).await?;
+
+    let mut latest: BTreeMap<String, ReleaseInfo> = BTreeMap::new();
+    for secret in secrets {
+        let info = match decode_release_secret(&secret) {
+            Ok(info) => info,
+            Err(err) => {
+                eprintln!(
+                    "⚠️  skipping secret '{}': {}",
+                    secret.name_any(),
+                    err
+                );
+                continue;
+            }
+        };
+
+        latest
+            .entry(info.name.clone())
+            .and_modify(|existing| {
+                if info.revision > existing.revision {
+                    *existing = info.clone();
+                }
+            })
+            .or_insert(info);
+    }
+
+    Ok(latest.into_values().collect())
+}
+
+/// Print a table of releases, mirroring Helm's own `helm list` output.
+pub fn print_release_table(releases: &[ReleaseInfo]) {
+    println!(
+        "{:<25} {:<15} {:<9} {:<15} {:<25} {:<12} UPDATED",
+        "NAME", "NAMESPACE", "REVISION", "STATUS", "CHART", "APP VERSION"
+    );
+    for r in releases {
+        println!(
+            "{:<25} {:<15} {:<9} {:<15} {:<25} {:<12} {}",
+            r.name,
+            r.namespace,
+            r.revision,
+            r.status,
+            format!("{}-{}", r.chart_name, r.chart_version),
+            r.app_version,
+            r.last_deployed,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use k8s_openapi::ByteString;
+    use std::io::Write;
+
+    /// Build a Secret whose `data["release"]` is `base64(gzip(json))`, i.e.
+    /// what kube-rs hands us after it already base64-decodes the Secret's
+    /// wire-format `data` map.
+    fn release_secret(json: &str) -> Secret {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(json.as_bytes()).unwrap();
+        let gzip_bytes = encoder.finish().unwrap();
+        let inner_b64 = STANDARD.encode(gzip_bytes);
+
+        let mut data = BTreeMap::new();
+        data.insert("release".to_string(), ByteString(inner_b64.into_bytes()));
+
+        Secret {
+            data: Some(data),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decodes_a_valid_release_payload() {
+        let secret = release_secret(
+            r#"{
+                "name": "my-app",
+                "version": 3,
+                "namespace": "prod",
+                "info": {"status": "deployed", "last_deployed": "2024-01-01T00:00:00Z"},
+                "chart": {"metadata": {"name": "my-chart", "version": "1.2.3", "appVersion": "4.5.6"}}
+            }"#,
+        );
+
+        let info = decode_release_secret(&secret).unwrap();
+        assert_eq!(info.name, "my-app");
+        assert_eq!(info.revision, 3);
+        assert_eq!(info.namespace, "prod");
+        assert_eq!(info.status, "deployed");
+        assert_eq!(info.chart_name, "my-chart");
+        assert_eq!(info.chart_version, "1.2.3");
+        assert_eq!(info.app_version, "4.5.6");
+        assert_eq!(info.last_deployed, "2024-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn defaults_missing_app_version() {
+        let secret = release_secret(
+            r#"{
+                "name": "my-app",
+                "version": 1,
+                "namespace": "default",
+                "info": {"status": "deployed", "last_deployed": "2024-01-01T00:00:00Z"},
+                "chart": {"metadata": {"name": "my-chart", "version": "1.0.0"}}
+            }"#,
+        );
+
+        let info = decode_release_secret(&secret).unwrap();
+        assert_eq!(info.app_version, "");
+    }
+
+    #[test]
+    fn rejects_secret_with_no_data() {
+        let secret = Secret::default();
+        assert!(decode_release_secret(&secret).is_err());
+    }
+
+    #[test]
+    fn rejects_secret_missing_release_key() {
+        let secret = Secret {
+            data: Some(BTreeMap::new()),
+            ..Default::default()
+        };
+        assert!(decode_release_secret(&secret).is_err());
+    }
+
+    #[test]
+    fn rejects_non_base64_payload() {
+        let mut data = BTreeMap::new();
+        data.insert("release".to_string(), ByteString(b"not base64!!".to_vec()));
+        let secret = Secret {
+            data: Some(data),
+            ..Default::default()
+        };
+        assert!(decode_release_secret(&secret).is_err());
+    }
+}
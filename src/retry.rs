@@ -0,0 +1,43 @@
+//! Exponential backoff retry wrapper for operations that can fail
+//! transiently against a flaky cluster (or one that another controller is
+//! concurrently mutating).
+
+use anyhow::Result;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const INITIAL_DELAY: Duration = Duration::from_secs(1);
+const MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Run `op` up to `retries + 1` times, doubling the delay between attempts
+/// (starting at 1s, capped at 30s) until it succeeds or the retry budget is
+/// exhausted.
+pub async fn retry_with_backoff<F, Fut, T>(retries: u32, what: &str, mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut delay = INITIAL_DELAY;
+    let mut attempt = 0;
+
+    loop {
+        attempt += 1;
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt <= retries => {
+                eprintln!(
+                    "⚠️  {} failed (attempt {}/{}): {}; retrying in {:?}",
+                    what,
+                    attempt,
+                    retries + 1,
+                    err,
+                    delay
+                );
+                sleep(delay).await;
+                delay = (delay * 2).min(MAX_DELAY);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
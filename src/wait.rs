@@ -0,0 +1,103 @@
+//! Polling helpers that block until a release's resources (and optionally
+//! its namespace) are actually gone from the cluster, since both `helm
+//! uninstall` and namespace deletion return before Kubernetes has finished
+//! reaping everything.
+
+use anyhow::{bail, Result};
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use k8s_openapi::api::core::v1::{ConfigMap, Namespace, Pod, Service};
+use kube::{api::ListParams, Api, Client, ResourceExt};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Poll the cluster until every Pod, Deployment, StatefulSet, Service, and
+/// ConfigMap owned by `release` in `ns` has disappeared, or until `timeout`
+/// elapses.
+pub async fn wait_for_release_gone(
+    client: &Client,
+    release: &str,
+    ns: &str,
+    timeout: Duration,
+) -> Result<()> {
+    let selector = format!(
+        "app.kubernetes.io/managed-by=Helm,app.kubernetes.io/instance={}",
+        release
+    );
+    let lp = ListParams::default().labels(&selector);
+
+    let pods: Api<Pod> = Api::namespaced(client.clone(), ns);
+    let deployments: Api<Deployment> = Api::namespaced(client.clone(), ns);
+    let stateful_sets: Api<StatefulSet> = Api::namespaced(client.clone(), ns);
+    let services: Api<Service> = Api::namespaced(client.clone(), ns);
+    let config_maps: Api<ConfigMap> = Api::namespaced(client.clone(), ns);
+
+    let start = Instant::now();
+    loop {
+        let mut lingering = Vec::new();
+
+        if !pods.list(&lp).await?.items.is_empty() {
+            lingering.push("Pods");
+        }
+        if !deployments.list(&lp).await?.items.is_empty() {
+            lingering.push("Deployments");
+        }
+        if !stateful_sets.list(&lp).await?.items.is_empty() {
+            lingering.push("StatefulSets");
+        }
+        if !services.list(&lp).await?.items.is_empty() {
+            lingering.push("Services");
+        }
+        if !config_maps.list(&lp).await?.items.is_empty() {
+            lingering.push("ConfigMaps");
+        }
+
+        if lingering.is_empty() {
+            return Ok(());
+        }
+
+        if start.elapsed() >= timeout {
+            bail!(
+                "timed out after {:?} waiting for release '{}' to be removed from '{}'; still present: {}",
+                timeout,
+                release,
+                ns,
+                lingering.join(", ")
+            );
+        }
+
+        sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Poll the cluster until Namespace `ns` is fully removed, or until `timeout`
+/// elapses. Namespace deletion is async and can hang on stuck finalizers, so
+/// the Namespace object can linger long after its resources are gone.
+pub async fn wait_for_namespace_gone(client: &Client, ns: &str, timeout: Duration) -> Result<()> {
+    let api: Api<Namespace> = Api::all(client.clone());
+    let start = Instant::now();
+
+    loop {
+        match api.get_opt(ns).await? {
+            None => return Ok(()),
+            Some(namespace) => {
+                if start.elapsed() >= timeout {
+                    let phase = namespace
+                        .status
+                        .as_ref()
+                        .and_then(|s| s.phase.clone())
+                        .unwrap_or_else(|| "Unknown".to_string());
+                    bail!(
+                        "timed out after {:?} waiting for namespace '{}' to be removed (phase: {}); it may have stuck finalizers",
+                        timeout,
+                        namespace.name_any(),
+                        phase
+                    );
+                }
+            }
+        }
+
+        sleep(POLL_INTERVAL).await;
+    }
+}